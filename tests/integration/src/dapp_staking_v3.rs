@@ -124,3 +124,205 @@ fn collator_selection_candidacy_not_possible_for_dapp_staking_participant() {
         );
     });
 }
+
+#[test]
+#[cfg(feature = "shibuya")]
+fn stake_on_registered_wasm_dapp_works() {
+    new_test_ext().execute_with(|| {
+        let (_, smart_contract) = deploy_and_register_wasm_dapp("flipper");
+
+        let amount = <Runtime as pallet_dapp_staking_v3::Config>::MinimumLockedAmount::get();
+        assert_ok!(DappStaking::lock(
+            RuntimeOrigin::signed(BOB.clone()),
+            amount
+        ));
+        assert_ok!(DappStaking::stake(
+            RuntimeOrigin::signed(BOB.clone()),
+            smart_contract.clone(),
+            amount,
+        ));
+
+        assert_eq!(
+            StakerInfo::<Runtime>::get(&BOB, &smart_contract)
+                .expect("Entry must exist after staking.")
+                .total_staked_amount(),
+            amount
+        );
+    });
+}
+
+#[test]
+fn run_to_block_fast_matches_full_runner_across_era_changes() {
+    let blocks_until_new_era = 20;
+    let end_state = |runner: fn(BlockNumber)| {
+        new_test_ext().execute_with(|| {
+            // Stake on a dApp, so the staker reward pool depends on the total issuance.
+            let smart_contract = SmartContract::Evm(H160::repeat_byte(0x01));
+            assert_ok!(DappStaking::register(
+                RuntimeOrigin::root(),
+                ALICE,
+                smart_contract.clone()
+            ));
+            let amount = INITIAL_AMOUNT / 2;
+            assert_ok!(DappStaking::lock(
+                RuntimeOrigin::signed(BOB.clone()),
+                amount
+            ));
+            assert_ok!(DappStaking::stake(
+                RuntimeOrigin::signed(BOB.clone()),
+                smart_contract,
+                amount,
+            ));
+
+            // Inflation recalculation also depends on the total issuance, trigger it at the end of the `Build&Earn` era.
+            pallet_inflation::ActiveInflationConfig::<Runtime>::mutate(|config| {
+                config.recalculation_era = 3;
+            });
+
+            // Shorten both the `Voting` and the `Build&Earn` era, otherwise the full runner is too slow.
+            ActiveProtocolState::<Runtime>::mutate(|state| {
+                state.next_era_start = System::block_number() + blocks_until_new_era;
+            });
+            runner(ActiveProtocolState::<Runtime>::get().next_era_start + 3);
+            assert_eq!(
+                ActiveProtocolState::<Runtime>::get().subperiod(),
+                Subperiod::BuildAndEarn,
+                "Sanity check."
+            );
+
+            ActiveProtocolState::<Runtime>::mutate(|state| {
+                state.next_era_start = System::block_number() + blocks_until_new_era;
+            });
+            let target_block = ActiveProtocolState::<Runtime>::get().next_era_start + 3;
+            runner(target_block);
+            assert_eq!(System::block_number(), target_block);
+            assert_eq!(
+                ActiveProtocolState::<Runtime>::get().era,
+                3,
+                "Sanity check."
+            );
+
+            (
+                System::block_number(),
+                Timestamp::get(),
+                Balances::total_issuance(),
+                ActiveProtocolState::<Runtime>::get(),
+                CurrentEraInfo::<Runtime>::get(),
+                EraRewards::<Runtime>::iter().collect::<Vec<_>>(),
+                DAppTiers::<Runtime>::iter().collect::<Vec<_>>(),
+                pallet_inflation::ActiveInflationConfig::<Runtime>::get(),
+            )
+        })
+    };
+
+    assert_eq!(end_state(run_to_block), end_state(run_to_block_fast));
+}
+
+#[test]
+fn starting_in_build_and_earn_subperiod_is_consistent() {
+    ExtBuilder::default()
+        .balances(vec![(ALICE, INITIAL_AMOUNT)])
+        .starting_subperiod(Subperiod::BuildAndEarn)
+        .build()
+        .execute_with(|| {
+            let era_length =
+                <Runtime as pallet_dapp_staking_v3::Config>::CycleConfiguration::blocks_per_era();
+            let build_and_earn_length_in_eras = <Runtime as pallet_dapp_staking_v3::Config>::CycleConfiguration::eras_per_build_and_earn_subperiod();
+
+            let protocol_state = ActiveProtocolState::<Runtime>::get();
+            assert_eq!(protocol_state.subperiod(), Subperiod::BuildAndEarn);
+            assert_eq!(protocol_state.period_number(), 1);
+            assert_eq!(protocol_state.era, 1);
+            assert_eq!(protocol_state.next_era_start, era_length + 1);
+            assert_eq!(
+                protocol_state.period_info.next_subperiod_start_era,
+                build_and_earn_length_in_eras + 1
+            );
+
+            // Regular era change must happen, without switching the subperiod.
+            run_to_block_fast(protocol_state.next_era_start);
+            let protocol_state = ActiveProtocolState::<Runtime>::get();
+            assert_eq!(protocol_state.era, 2);
+            assert_eq!(protocol_state.subperiod(), Subperiod::BuildAndEarn);
+            assert_eq!(protocol_state.period_number(), 1);
+
+            // Remaining `Build&Earn` eras, new period must start with the recomputed subperiod start era.
+            while ActiveProtocolState::<Runtime>::get().era < build_and_earn_length_in_eras + 1 {
+                let protocol_state = ActiveProtocolState::<Runtime>::get();
+                assert_eq!(protocol_state.subperiod(), Subperiod::BuildAndEarn);
+                run_to_block_fast(protocol_state.next_era_start);
+            }
+            let protocol_state = ActiveProtocolState::<Runtime>::get();
+            assert_eq!(protocol_state.era, build_and_earn_length_in_eras + 1);
+            assert_eq!(protocol_state.subperiod(), Subperiod::Voting);
+            assert_eq!(protocol_state.period_number(), 2);
+        });
+}
+
+#[test]
+fn block_rewards_are_issued_across_era_change() {
+    new_test_ext().execute_with(|| {
+        let inflation_config = pallet_inflation::ActiveInflationConfig::<Runtime>::get();
+        let reward_per_block =
+            inflation_config.collator_reward_per_block + inflation_config.treasury_reward_per_block;
+        assert!(reward_per_block > 0, "Sanity check.");
+
+        // Voting subperiod is a prolonged era, cut it short so the first regular era is reached quickly.
+        ActiveProtocolState::<Runtime>::mutate(|state| {
+            state.next_era_start = System::block_number() + 10;
+        });
+        let init_era = ActiveProtocolState::<Runtime>::get().era;
+        let init_block = System::block_number();
+        let init_issuance = Balances::total_issuance();
+
+        // Each block, including the one starting the new era, must pay out the configured block rewards.
+        run_to_block(ActiveProtocolState::<Runtime>::get().next_era_start);
+        assert_eq!(ActiveProtocolState::<Runtime>::get().era, init_era + 1);
+
+        let produced_blocks = System::block_number() - init_block;
+        assert_eq!(
+            Balances::total_issuance(),
+            init_issuance + reward_per_block * Balance::from(produced_blocks)
+        );
+
+        // Advance through a full, regular era.
+        // Issuance per era must be equal to the block rewards of all the blocks in the era.
+        let era_length =
+            <Runtime as pallet_dapp_staking_v3::Config>::CycleConfiguration::blocks_per_era();
+        let era_start_issuance = Balances::total_issuance();
+
+        run_to_block(ActiveProtocolState::<Runtime>::get().next_era_start);
+        assert_eq!(ActiveProtocolState::<Runtime>::get().era, init_era + 2);
+        assert_eq!(
+            Balances::total_issuance(),
+            era_start_issuance + reward_per_block * Balance::from(era_length)
+        );
+
+        assert_eq!(
+            inflation_config,
+            pallet_inflation::ActiveInflationConfig::<Runtime>::get(),
+            "Inflation config must not change outside of recalculation era."
+        );
+    });
+}
+
+#[test]
+fn custom_block_time_is_used_by_block_runner() {
+    let custom_block_time = BLOCK_TIME / 2;
+    let mut custom_ext = ExtBuilder::default().block_time(custom_block_time).build();
+    // Building another externalities must not affect the already built one.
+    let mut default_ext = new_test_ext();
+
+    custom_ext.execute_with(|| {
+        run_to_block(3);
+        assert_eq!(Timestamp::get(), 3 * custom_block_time);
+
+        run_for_blocks(2);
+        assert_eq!(Timestamp::get(), 5 * custom_block_time);
+    });
+
+    default_ext.execute_with(|| {
+        run_to_block(3);
+        assert_eq!(Timestamp::get(), 3 * BLOCK_TIME);
+    });
+}
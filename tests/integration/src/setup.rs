@@ -28,8 +28,12 @@ pub use sp_core::{H160, H256, U256};
 pub use sp_io::hashing::keccak_256;
 pub use sp_runtime::{AccountId32, MultiAddress};
 
+use frame_support::traits::Currency;
+
 pub use astar_primitives::{
-    dapp_staking::CycleConfiguration, evm::UnifiedAddressMapper, BlockNumber,
+    dapp_staking::{CycleConfiguration, SmartContract},
+    evm::UnifiedAddressMapper,
+    BlockNumber,
 };
 
 #[cfg(feature = "shibuya")]
@@ -99,6 +103,24 @@ mod shibuya {
         address
     }
 
+    /// Deploy a WASM contract via ALICE as origin and register it for dApp staking, with ALICE as the owner.
+    pub fn deploy_and_register_wasm_dapp(name: &str) -> (AccountId32, SmartContract<AccountId32>) {
+        let address = deploy_wasm_contract(name);
+        let smart_contract = SmartContract::Wasm(address.clone());
+
+        assert_ok!(DappStaking::register(
+            RuntimeOrigin::root(),
+            ALICE,
+            smart_contract.clone()
+        ));
+        assert!(
+            pallet_dapp_staking_v3::IntegratedDApps::<Runtime>::contains_key(&smart_contract),
+            "dApp registration failed."
+        );
+
+        (address, smart_contract)
+    }
+
     /// Call a wasm smart contract method
     pub fn call_wasm_contract_method<V: Decode>(
         origin: AccountId,
@@ -168,11 +190,17 @@ pub use pallet_utility::{Call as UtilityCall, Event as UtilityEvent};
 
 pub struct ExtBuilder {
     balances: Vec<(AccountId32, Balance)>,
+    subperiod: pallet_dapp_staking_v3::Subperiod,
+    block_time: u64,
 }
 
 impl Default for ExtBuilder {
     fn default() -> Self {
-        Self { balances: vec![] }
+        Self {
+            balances: vec![],
+            subperiod: pallet_dapp_staking_v3::Subperiod::Voting,
+            block_time: BLOCK_TIME,
+        }
     }
 }
 
@@ -182,6 +210,21 @@ impl ExtBuilder {
         self
     }
 
+    /// Subperiod of the first dApp staking period. `Voting` by default.
+    pub fn starting_subperiod(mut self, subperiod: pallet_dapp_staking_v3::Subperiod) -> Self {
+        self.subperiod = subperiod;
+        self
+    }
+
+    /// Block time, in milliseconds, used by `run_to_block` to set the timestamp. 12 seconds by default.
+    ///
+    /// Era length, in blocks, is defined by the runtime's `CycleConfiguration` and isn't affected by this.
+    /// To shorten an era, modify `next_era_start` of the dApp staking `ActiveProtocolState` instead.
+    pub fn block_time(mut self, block_time: u64) -> Self {
+        self.block_time = block_time;
+        self
+    }
+
     pub fn build(self) -> sp_io::TestExternalities {
         let mut t = frame_system::GenesisConfig::default()
             .build_storage::<Runtime>()
@@ -203,18 +246,33 @@ impl ExtBuilder {
         let mut ext = sp_io::TestExternalities::new(t);
         ext.execute_with(|| {
             System::set_block_number(1);
+            BlockTime::set(&self.block_time);
 
             let era_length = <Runtime as pallet_dapp_staking_v3::Config>::CycleConfiguration::blocks_per_era();
             let voting_period_length_in_eras =
             <Runtime as pallet_dapp_staking_v3::Config>::CycleConfiguration::eras_per_voting_subperiod();
+            let build_and_earn_period_length_in_eras =
+            <Runtime as pallet_dapp_staking_v3::Config>::CycleConfiguration::eras_per_build_and_earn_subperiod();
+
+            // Voting subperiod lasts for one 'prolonged' era, while `Build&Earn` consists of regular eras.
+            let (next_era_start, next_subperiod_start_era) = match self.subperiod {
+                pallet_dapp_staking_v3::Subperiod::Voting => (
+                    era_length.saturating_mul(voting_period_length_in_eras.into()) + 1,
+                    2,
+                ),
+                pallet_dapp_staking_v3::Subperiod::BuildAndEarn => (
+                    era_length + 1,
+                    build_and_earn_period_length_in_eras + 1,
+                ),
+            };
 
             pallet_dapp_staking_v3::ActiveProtocolState::<Runtime>::put(pallet_dapp_staking_v3::ProtocolState {
                 era: 1,
-                next_era_start: era_length.saturating_mul(voting_period_length_in_eras.into()) + 1,
+                next_era_start,
                 period_info: pallet_dapp_staking_v3::PeriodInfo {
                     number: 1,
-                    subperiod: pallet_dapp_staking_v3::Subperiod::Voting,
-                    next_subperiod_start_era: 2,
+                    subperiod: self.subperiod,
+                    next_subperiod_start_era,
                 },
                 maintenance: false,
             });
@@ -237,6 +295,12 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 // Block time: 12 seconds.
 pub const BLOCK_TIME: u64 = 12_000;
 
+frame_support::parameter_types! {
+    /// Block time, in milliseconds, configured via `ExtBuilder`.
+    /// Kept in the externalities storage, so each built externalities uses its own value.
+    pub storage BlockTime: u64 = BLOCK_TIME;
+}
+
 pub fn run_to_block(n: BlockNumber) {
     while System::block_number() < n {
         let block_number = System::block_number();
@@ -255,7 +319,7 @@ pub fn run_to_block(n: BlockNumber) {
         let block_number = System::block_number();
 
         Inflation::on_initialize(block_number);
-        Timestamp::set_timestamp(block_number as u64 * BLOCK_TIME);
+        Timestamp::set_timestamp(block_number as u64 * BlockTime::get());
         TransactionPayment::on_initialize(block_number);
         DappStaking::on_initialize(block_number);
         Authorship::on_initialize(block_number);
@@ -277,6 +341,54 @@ pub fn run_for_blocks(n: BlockNumber) {
     run_to_block(System::block_number() + n)
 }
 
+/// Faster alternative to `run_to_block`, intended for tests which need to advance through many eras.
+///
+/// Blocks which cannot trigger a dApp staking era or period change are skipped by only bumping the block number.
+/// The block right before the new era start, the new era start block itself and the target block `n`
+/// are executed via `run_to_block`.
+///
+/// Block rewards of the skipped blocks are paid out in bulk, through the same payout path as `Inflation::on_initialize`,
+/// so total issuance, dApp staking protocol state, era rewards and inflation recalculation end up the same as with the full runner.
+///
+/// Skipped blocks don't execute any other per-block logic (e.g. session rotation, collator selection, XCM queue processing),
+/// and no timestamp is set for them, so state related to those will differ from the full runner.
+pub fn run_to_block_fast(n: BlockNumber) {
+    while System::block_number() < n {
+        let now = System::block_number();
+        let next_era_start =
+            pallet_dapp_staking_v3::ActiveProtocolState::<Runtime>::get().next_era_start;
+
+        // Era isn't progressing (e.g. maintenance mode), nothing can be skipped.
+        if next_era_start <= now {
+            run_to_block(n);
+            return;
+        }
+
+        // The first hook which does any era related work is executed in the block before the new era start.
+        // Never skip over the target block itself, it must be initialized like any other executed block.
+        let skip_to = next_era_start.saturating_sub(2).min(n.saturating_sub(1));
+        if skip_to > now {
+            payout_block_rewards(skip_to - now);
+            System::set_block_number(skip_to);
+        }
+
+        run_to_block(next_era_start.min(n));
+    }
+}
+
+/// Pay out block rewards for the given number of blocks at once, as `Inflation::on_initialize` would do for each of them.
+fn payout_block_rewards(blocks: BlockNumber) {
+    let config = pallet_inflation::ActiveInflationConfig::<Runtime>::get();
+    let blocks = Balance::from(blocks);
+
+    let collator_amount = Balances::issue(config.collator_reward_per_block * blocks);
+    let treasury_amount = Balances::issue(config.treasury_reward_per_block * blocks);
+
+    type Payout = <Runtime as pallet_inflation::Config>::PayoutPerBlock;
+    <Payout as pallet_inflation::PayoutPerBlock<_>>::collators(collator_amount);
+    <Payout as pallet_inflation::PayoutPerBlock<_>>::treasury(treasury_amount);
+}
+
 fn last_events(n: usize) -> Vec<RuntimeEvent> {
     frame_system::Pallet::<Runtime>::events()
         .into_iter()